@@ -1,9 +1,15 @@
-use std::path::PathBuf;
-
-use clap::Parser;
-use image::{imageops::FilterType, DynamicImage, ImageBuffer, ImageFormat, Rgba};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use clap::{Parser, ValueEnum};
+#[cfg(feature = "raw")]
+use image::Rgb;
+use image::{
+    codecs::jpeg::JpegEncoder, imageops::FilterType, DynamicImage, ImageBuffer, ImageFormat, Rgba,
+};
 use indicatif::ProgressBar;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
 
 /// A poster/painting generation tool for Lethal Posters and Lethal Paintings
 #[derive(Parser, Debug)]
@@ -20,6 +26,68 @@ struct Args {
     /// The directory containing the images to generate posters and paintings for
     #[arg(short, long, default_value_t = String::from("./output"))]
     output: String,
+
+    /// The output image format for generated posters, tips, and paintings
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Png)]
+    format: OutputFormat,
+
+    /// The quality (1-100) to use for lossy output formats (webp, jpeg)
+    #[arg(short, long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    quality: u8,
+
+    /// A TOML file describing the template filenames and atlas/tips/painting geometry,
+    /// falling back to the built-in defaults when omitted
+    #[arg(short, long)]
+    config: Option<String>,
+
+    /// The number of worker threads to use for generation (0 = all available cores)
+    #[arg(short = 'j', long, default_value_t = 0)]
+    threads: usize,
+
+    /// The minimum severity of log records emitted to stderr
+    #[arg(short = 'l', long, value_enum, default_value_t = LogLevel::Info)]
+    log_level: LogLevel,
+}
+
+/// The minimum severity of log records emitted to stderr
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for log::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+/// The image format to encode generated posters, tips, and paintings as
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Png,
+    Webp,
+    Jpeg,
+}
+
+impl OutputFormat {
+    /// The file extension to use for an image saved in this format
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Webp => "webp",
+            OutputFormat::Jpeg => "jpg",
+        }
+    }
 }
 
 const TEMPLATE_POSTER: &str = "poster_template.png";
@@ -28,61 +96,205 @@ const POSTERS_OUT_DIR: &str = "BepInEx/plugins/LethalPosters/posters";
 const TIPS_OUT_DIR: &str = "BepInEx/plugins/LethalPosters/tips";
 const PAINTINGS_OUT_DIR: &str = "BepInEx/plugins/LethalPaintings/paintings";
 
+/// The resize filter used to fit every input image into its poster/tips/painting slot,
+/// recorded in `manifest.json` for reproducibility
+const RESIZE_FILTER: FilterType = FilterType::Lanczos3;
+
+/// The default five poster rectangles (`[x, y, w, h]`) carved out of `poster_template.png`
+const DEFAULT_POSTER_OFFSETS: [[u32; 4]; 5] = [
+    [0, 0, 341, 559],
+    [346, 0, 284, 559],
+    [641, 58, 274, 243],
+    [184, 620, 411, 364],
+    [632, 320, 372, 672],
+];
+
+/// Template filenames and atlas/tips/painting geometry, loadable from a `--config` TOML
+/// file so new template packs don't require a recompile. Missing fields fall back to the
+/// values baked into this tool for the stock Lethal Posters templates.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+struct LayoutConfig {
+    /// Filename of the poster atlas template, relative to `--templates`
+    template_poster: String,
+    /// Filename of the painting template, relative to `--templates`
+    template_painting: String,
+    /// The five poster rectangles carved out of `template_poster`, each `[x, y, w, h]`
+    poster_offsets: Vec<[u32; 4]>,
+    tips: TipsLayout,
+    painting: PaintingLayout,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            template_poster: TEMPLATE_POSTER.to_string(),
+            template_painting: TEMPLATE_PAINTING.to_string(),
+            poster_offsets: DEFAULT_POSTER_OFFSETS.to_vec(),
+            tips: TipsLayout::default(),
+            painting: PaintingLayout::default(),
+        }
+    }
+}
+
+/// Tips image canvas size and the anchor the resized poster is right/top-aligned against
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+struct TipsLayout {
+    /// Width of the tips canvas, in pixels
+    width: u32,
+    /// Height of the tips canvas, in pixels
+    height: u32,
+    /// Right edge (in canvas pixels) the resized poster is right-aligned against; the
+    /// poster's overlay x is `anchor_x - resized_width` (clamped at 0), so this should track
+    /// `width` whenever it changes or the poster is misplaced
+    anchor_x: u32,
+    /// Top edge (in canvas pixels) the resized poster is overlaid at. Signed, like every
+    /// other overlay origin in this file, because `image::imageops::overlay` takes `i64`
+    anchor_y: i64,
+}
+
+impl Default for TipsLayout {
+    fn default() -> Self {
+        TipsLayout {
+            width: 796,
+            height: 1024,
+            anchor_x: 796,
+            anchor_y: 0,
+        }
+    }
+}
+
+/// Painting fill size and the origin the resized poster is overlaid at on the template
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+struct PaintingLayout {
+    /// Width the poster is `resize_to_fill`'d to before being overlaid on the template
+    fill_width: u32,
+    /// Height the poster is `resize_to_fill`'d to before being overlaid on the template
+    fill_height: u32,
+    /// X origin, in template pixels from the top-left corner, the filled poster is overlaid at
+    overlay_x: i64,
+    /// Y origin, in template pixels from the top-left corner, the filled poster is overlaid at
+    overlay_y: i64,
+}
+
+impl Default for PaintingLayout {
+    fn default() -> Self {
+        PaintingLayout {
+            fill_width: 243,
+            fill_height: 324,
+            overlay_x: 264,
+            overlay_y: 19,
+        }
+    }
+}
+
+/// Load the layout config from `--config`, falling back to the built-in defaults when unset
+fn load_layout_config(config_path: &Option<String>) -> LayoutConfig {
+    let Some(path) = config_path else {
+        return LayoutConfig::default();
+    };
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read layout config \"{path}\": {e:?}"));
+    toml::from_str(&contents)
+        .unwrap_or_else(|e| panic!("Failed to parse layout config \"{path}\": {e:?}"))
+}
+
 fn main() {
     let args = Args::parse();
-    println!("Parsed args: {args:?}");
+    env_logger::Builder::new()
+        .filter_level(args.log_level.into())
+        .init();
+    log::debug!("Parsed args: {args:?}");
+
+    // Load layout config
+    let layout = load_layout_config(&args.config);
+    let poster_offsets: [[u32; 4]; 5] =
+        layout
+            .poster_offsets
+            .clone()
+            .try_into()
+            .unwrap_or_else(|offsets: Vec<[u32; 4]>| {
+                panic!(
+                    "Layout config must specify exactly 5 poster_offsets, got {}",
+                    offsets.len()
+                )
+            });
 
     // Resolve paths
-    println!("Resolving paths...");
+    log::info!("Resolving paths...");
     let template_dir = PathBuf::from(args.templates);
     let input_dir = PathBuf::from(args.input);
     let output_dir = PathBuf::from(args.output);
-    let poster_template_path = get_path(&template_dir, TEMPLATE_POSTER);
-    let painting_template_path = get_path(&template_dir, TEMPLATE_PAINTING);
+    let poster_template_path = get_path(&template_dir, &layout.template_poster);
+    let painting_template_path = get_path(&template_dir, &layout.template_painting);
     let posters_dir = create_dir_and_get_path(&output_dir, POSTERS_OUT_DIR);
     let tips_dir = create_dir_and_get_path(&output_dir, TIPS_OUT_DIR);
     let paintings_dir = create_dir_and_get_path(&output_dir, PAINTINGS_OUT_DIR);
 
-    // Load images
-    println!("Loading images...");
-    let poster_template: DynamicImage = image::open(&poster_template_path)
-        .unwrap_or_else(|e| panic!("Failed to open poster template image: {e:?}"));
-    let painting_template: DynamicImage = image::open(&painting_template_path)
-        .unwrap_or_else(|e| panic!("Failed to open painting template image: {e:?}"));
-    let input_imgs = load_input_imgs(&input_dir);
-
-    // Generate posters and paintings
-    generate_assets(
-        posters_dir,
-        tips_dir,
-        paintings_dir,
-        poster_template,
-        painting_template,
-        input_imgs,
-    );
-
-    println!(
-        "Operation complete! Images output to: {}",
-        output_dir.to_str().unwrap_or("???")
-    );
+    // Build the bounded worker pool generation runs inside (0 = let rayon use all cores)
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads)
+        .build()
+        .unwrap_or_else(|e| panic!("Failed to build thread pool: {e:?}"));
+
+    pool.install(|| {
+        // Load images
+        log::info!("Loading images...");
+        let poster_template: DynamicImage = image::open(&poster_template_path)
+            .unwrap_or_else(|e| panic!("Failed to open poster template image: {e:?}"));
+        let painting_template: DynamicImage = image::open(&painting_template_path)
+            .unwrap_or_else(|e| panic!("Failed to open painting template image: {e:?}"));
+        let (input_names, input_imgs) = load_input_imgs(&input_dir);
+
+        // Generate posters and paintings
+        generate_assets(
+            &output_dir,
+            posters_dir,
+            tips_dir,
+            paintings_dir,
+            poster_template,
+            painting_template,
+            input_names,
+            input_imgs,
+            args.format,
+            args.quality,
+            poster_offsets,
+            layout.tips,
+            layout.painting,
+        );
+
+        log::info!(
+            "Operation complete! Images output to: {}",
+            output_dir.to_str().unwrap_or("???")
+        );
+    });
 }
 
 #[inline]
-fn get_path(base: &PathBuf, sub_path: &str) -> PathBuf {
-    let mut path = base.clone();
+fn get_path(base: &Path, sub_path: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
     path.push(sub_path);
     path
 }
 
 #[inline]
-fn create_dir_and_get_path(base: &PathBuf, sub_path: &str) -> PathBuf {
+fn create_dir_and_get_path(base: &Path, sub_path: &str) -> PathBuf {
     let path = get_path(base, sub_path);
     std::fs::create_dir_all(&path)
         .unwrap_or_else(|e| panic!("Failed to create output directory for \"{sub_path}\": {e:?}"));
     path
 }
 
-fn load_input_imgs(input_dir: &PathBuf) -> Vec<DynamicImage> {
+/// File extensions decoded via the `raw` feature's RAW demosaicing pipeline instead of `image::open`
+const RAW_EXTENSIONS: &[&str] = &["cr2", "nef", "dng", "arw", "raf", "orf", "rw2"];
+/// File extensions decoded via the `heif` feature's libheif binding instead of `image::open`
+const HEIF_EXTENSIONS: &[&str] = &["heic", "heif"];
+
+/// Load every decodable image in `input_dir`, paired with the filename it came from so the
+/// manifest can record provenance by index.
+fn load_input_imgs(input_dir: &Path) -> (Vec<String>, Vec<DynamicImage>) {
     std::fs::read_dir(input_dir)
         .unwrap_or_else(|e| {
             panic!(
@@ -94,88 +306,285 @@ fn load_input_imgs(input_dir: &PathBuf) -> Vec<DynamicImage> {
         .map(|entry| entry.path())
         .collect::<Vec<PathBuf>>()
         .par_iter()
-        .filter_map(|path| image::open(path).ok())
-        .collect()
+        .filter_map(|path| open_input_img(path))
+        .unzip()
+}
+
+/// Open a single input image, routing RAW and HEIF extensions through their dedicated
+/// decoders so they aren't silently dropped by `image::open`. Returns the source filename
+/// alongside the decoded image, logging a warning (rather than aborting the run) if the
+/// file can't be decoded.
+fn open_input_img(path: &Path) -> Option<(String, DynamicImage)> {
+    let Some(extension) = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+    else {
+        log::warn!(
+            "Skipping input with no recognizable extension: {}",
+            path.display()
+        );
+        return None;
+    };
+    let Some(name) = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_string)
+    else {
+        log::warn!("Skipping input with non-UTF8 filename: {}", path.display());
+        return None;
+    };
+    let img = if RAW_EXTENSIONS.contains(&extension.as_str()) {
+        open_raw_img(path)
+    } else if HEIF_EXTENSIONS.contains(&extension.as_str()) {
+        open_heif_img(path)
+    } else {
+        image::open(path).ok()
+    };
+    if img.is_none() {
+        log::warn!("Failed to decode input image: {}", path.display());
+    }
+    img.map(|img| (name, img))
+}
+
+/// Demosaic and develop a RAW sensor image into an 8-bit RGB `DynamicImage`.
+#[cfg(feature = "raw")]
+fn open_raw_img(path: &Path) -> Option<DynamicImage> {
+    let developed = imagepipe::simple_decode_8bit(path, 0, 0).ok()?;
+    let buffer = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_raw(
+        developed.width as u32,
+        developed.height as u32,
+        developed.data,
+    )?;
+    Some(DynamicImage::ImageRgb8(buffer))
 }
 
+#[cfg(not(feature = "raw"))]
+fn open_raw_img(_path: &Path) -> Option<DynamicImage> {
+    None
+}
+
+/// Decode a HEIF/HEIC image via libheif into an 8-bit RGBA `DynamicImage`.
+#[cfg(feature = "heif")]
+fn open_heif_img(path: &Path) -> Option<DynamicImage> {
+    let ctx = libheif_rs::HeifContext::read_from_file(path.to_str()?).ok()?;
+    let handle = ctx.primary_image_handle().ok()?;
+    let heif_img = handle
+        .decode(
+            libheif_rs::ColorSpace::Rgb(libheif_rs::RgbChroma::Rgba),
+            None,
+        )
+        .ok()?;
+    let plane = heif_img.planes().interleaved?;
+    // `plane.data` is padded to `plane.stride` bytes per row, which can exceed
+    // `width * 4` for real-world photos; strip the padding before handing the
+    // buffer to `from_raw`, which requires a tightly packed `width * height * 4`.
+    let row_bytes = plane.width as usize * 4;
+    let mut data = Vec::with_capacity(row_bytes * plane.height as usize);
+    for row in 0..plane.height as usize {
+        let start = row * plane.stride;
+        data.extend_from_slice(&plane.data[start..start + row_bytes]);
+    }
+    let buffer = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(plane.width, plane.height, data)?;
+    Some(DynamicImage::ImageRgba8(buffer))
+}
+
+#[cfg(not(feature = "heif"))]
+fn open_heif_img(_path: &Path) -> Option<DynamicImage> {
+    None
+}
+
+// One call site, fully internal to this binary — splitting this into a config struct
+// would add indirection without a second caller to justify it.
+#[allow(clippy::too_many_arguments)]
 fn generate_assets(
+    output_dir: &Path,
     posters_dir: PathBuf,
     tips_dir: PathBuf,
     paintings_dir: PathBuf,
     poster_template: DynamicImage,
     painting_template: DynamicImage,
+    input_names: Vec<String>,
     input_imgs: Vec<DynamicImage>,
+    format: OutputFormat,
+    quality: u8,
+    poster_offsets: [[u32; 4]; 5],
+    tips_layout: TipsLayout,
+    painting_layout: PaintingLayout,
 ) {
     // Create progress bar
     let img_count = input_imgs.len();
-    println!("Generating {img_count} posters and paintings...");
+    log::info!("Generating {img_count} posters and paintings...");
     let bar: ProgressBar = ProgressBar::new(img_count as u64);
+    let failures = AtomicU64::new(0);
 
     // Generate posters/paintings
-    input_imgs.par_iter().enumerate().for_each(|(i, _)| {
-        // Output paths for this asset
-        let img_name = format!("{i}.png");
-        let poster_path = get_path(&posters_dir, &img_name);
-        let tips_path = get_path(&tips_dir, &img_name);
-        let painting_path = get_path(&paintings_dir, &img_name);
-
-        rayon::scope(|s| {
-            // Generate atlas
-            s.spawn(|_| {
-                generate_atlas(
-                    &poster_template,
-                    &[
+    let manifest: Vec<ManifestEntry> = input_imgs
+        .par_iter()
+        .enumerate()
+        .filter_map(|(i, _)| {
+            // Output paths for this asset
+            let img_name = format!("{i}.{}", format.extension());
+            let poster_path = get_path(&posters_dir, &img_name);
+            let tips_path = get_path(&tips_dir, &img_name);
+            let painting_path = get_path(&paintings_dir, &img_name);
+            let asset_failed = AtomicBool::new(false);
+
+            rayon::scope(|s| {
+                // Generate atlas
+                s.spawn(|_| {
+                    let atlas = generate_atlas(
+                        &poster_template,
+                        &[
+                            &input_imgs[i % img_count],
+                            &input_imgs[(i + 1) % img_count],
+                            &input_imgs[(i + 2) % img_count],
+                            &input_imgs[(i + 3) % img_count],
+                            &input_imgs[(i + 4) % img_count],
+                        ],
+                        &poster_offsets,
+                    );
+                    if let Err(e) = save_image(&atlas, &poster_path, format, quality, false) {
+                        log::error!("Failed to generate poster atlas for index {i}: {e}");
+                        asset_failed.store(true, Ordering::Relaxed);
+                    }
+                });
+
+                // Generate tips
+                s.spawn(|_| {
+                    let tips =
+                        DynamicImage::from(generate_tips(&input_imgs[i % img_count], tips_layout));
+                    if let Err(e) = save_image(&tips, &tips_path, format, quality, true) {
+                        log::error!("Failed to generate tips for index {i}: {e}");
+                        asset_failed.store(true, Ordering::Relaxed);
+                    }
+                });
+
+                // Generate painting
+                s.spawn(|_| {
+                    let painting = generate_painting(
+                        &painting_template,
                         &input_imgs[i % img_count],
-                        &input_imgs[(i + 1) % img_count],
-                        &input_imgs[(i + 2) % img_count],
-                        &input_imgs[(i + 3) % img_count],
-                        &input_imgs[(i + 4) % img_count],
-                    ],
-                )
-                .save_with_format(&poster_path, ImageFormat::Png)
-                .unwrap_or_else(|e| panic!("Failed to generate poster atlas: {e:?}"));
-            });
-
-            // Generate tips
-            s.spawn(|_| {
-                generate_tips(&input_imgs[i % img_count])
-                    .save_with_format(&tips_path, ImageFormat::Png)
-                    .unwrap_or_else(|e| panic!("Failed to generate tips: {e:?}"));
-            });
-
-            // Generate painting
-            s.spawn(|_| {
-                generate_painting(&painting_template, &input_imgs[i % img_count])
-                    .save_with_format(&painting_path, ImageFormat::Png)
-                    .unwrap_or_else(|e| panic!("Failed to generate painting: {e:?}"));
+                        painting_layout,
+                    );
+                    if let Err(e) = save_image(&painting, &painting_path, format, quality, false) {
+                        log::error!("Failed to generate painting for index {i}: {e}");
+                        asset_failed.store(true, Ordering::Relaxed);
+                    }
+                });
+
+                // Update progress bar
+                bar.inc(1);
             });
 
-            // Update progress bar
-            bar.inc(1);
+            if asset_failed.load(Ordering::Relaxed) {
+                failures.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+
+            Some(ManifestEntry {
+                index: i,
+                poster_sources: (0..5)
+                    .map(|offset| input_names[(i + offset) % img_count].clone())
+                    .collect(),
+                tips_source: input_names[i % img_count].clone(),
+                painting_source: input_names[i % img_count].clone(),
+                resize_filter: format!("{:?}", RESIZE_FILTER),
+                poster_path,
+                tips_path,
+                painting_path,
+            })
         })
-    });
+        .collect();
 
     // Finish progress bar
     bar.finish_with_message("Image generation complete!");
+
+    // Summarize failures
+    let failure_count = failures.load(Ordering::Relaxed);
+    if failure_count > 0 {
+        log::warn!("{failure_count} of {img_count} assets failed to generate; see errors above");
+    } else {
+        log::info!("All {img_count} assets generated successfully");
+    }
+
+    // Write manifest
+    write_manifest(output_dir, &manifest);
 }
 
-const POSTER_OFFSETS: &[&[u32; 4]; 5] = &[
-    &[0, 0, 341, 559],
-    &[346, 0, 284, 559],
-    &[641, 58, 274, 243],
-    &[184, 620, 411, 364],
-    &[632, 320, 372, 672],
-];
+/// One generated poster/tips/painting set, recording which input(s) produced it so a pack
+/// author can regenerate or attribute individual images without re-deriving the indexing by hand.
+#[derive(Debug, Serialize)]
+struct ManifestEntry {
+    index: usize,
+    poster_sources: Vec<String>,
+    tips_source: String,
+    painting_source: String,
+    resize_filter: String,
+    poster_path: PathBuf,
+    tips_path: PathBuf,
+    painting_path: PathBuf,
+}
+
+/// Write `manifest.json` at the output root, each entry recording its numeric asset index;
+/// assets that failed to generate have no entry
+fn write_manifest(output_dir: &Path, manifest: &[ManifestEntry]) {
+    let manifest_path = get_path(output_dir, "manifest.json");
+    let contents = serde_json::to_string_pretty(manifest)
+        .unwrap_or_else(|e| panic!("Failed to serialize manifest: {e:?}"));
+    std::fs::write(&manifest_path, contents)
+        .unwrap_or_else(|e| panic!("Failed to write manifest: {e:?}"));
+}
 
-fn generate_atlas(template: &DynamicImage, posters: &[&DynamicImage; 5]) -> DynamicImage {
+/// Encode and save `img` to `path` in the requested `format`.
+///
+/// `quality` (1-100) controls lossy encoders (`webp`, `jpeg`). `preserve_alpha` forces
+/// a lossless `webp` encode, e.g. for the transparent padding around a tips image. It has
+/// no effect on `png` (always lossless) or `jpeg`, which is always encoded lossy as RGB
+/// and drops the alpha channel.
+fn save_image(
+    img: &DynamicImage,
+    path: &Path,
+    format: OutputFormat,
+    quality: u8,
+    preserve_alpha: bool,
+) -> Result<(), String> {
+    match format {
+        OutputFormat::Png => img
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(|e| format!("{e:?}")),
+        OutputFormat::Jpeg => {
+            let rgb = img.to_rgb8();
+            let mut out = std::fs::File::create(path).map_err(|e| format!("{e:?}"))?;
+            JpegEncoder::new_with_quality(&mut out, quality)
+                .encode_image(&rgb)
+                .map_err(|e| format!("{e:?}"))
+        }
+        OutputFormat::Webp => {
+            let encoder = webp::Encoder::from_image(img).map_err(|e| e.to_string())?;
+            let encoded = if preserve_alpha {
+                encoder.encode_lossless()
+            } else {
+                encoder.encode(quality as f32)
+            };
+            std::fs::write(path, &*encoded).map_err(|e| format!("{e:?}"))
+        }
+    }
+}
+
+fn generate_atlas(
+    template: &DynamicImage,
+    posters: &[&DynamicImage; 5],
+    poster_offsets: &[[u32; 4]; 5],
+) -> DynamicImage {
     let mut base = template.clone();
 
     // Generate overlays by resizing the image
-    let overlays: Vec<(DynamicImage, (i64, i64))> = POSTER_OFFSETS
+    let overlays: Vec<(DynamicImage, (i64, i64))> = poster_offsets
         .par_iter()
         .enumerate()
         .map(|(i, &o)| {
-            let resized_img = posters[i].resize(o[2], o[3], FilterType::Lanczos3);
+            let resized_img = posters[i].resize(o[2], o[3], RESIZE_FILTER);
             let x = (o[0] + o[2] - resized_img.width()) as i64;
             let y = o[1] as i64;
             (resized_img, (x, y)) // Return the resized image and its overlay position
@@ -191,28 +600,40 @@ fn generate_atlas(template: &DynamicImage, posters: &[&DynamicImage; 5]) -> Dyna
     base
 }
 
-fn generate_tips(poster: &DynamicImage) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+fn generate_tips(poster: &DynamicImage, layout: TipsLayout) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
     // Create base image
-    let mut base = ImageBuffer::new(796, 1024);
+    let mut base = ImageBuffer::new(layout.width, layout.height);
 
     // Resize poster image
-    let resized_poster = poster.resize(796, 1024, FilterType::Lanczos3);
+    let resized_poster = poster.resize(layout.width, layout.height, RESIZE_FILTER);
 
     // Overlay poster onto base
-    let x = (796 - resized_poster.width()) as i64;
-    image::imageops::overlay(&mut base, &resized_poster, x, 0);
+    // NOTE: saturating because a config with anchor_x smaller than the resized poster
+    // (e.g. anchor_x not updated alongside width/height) must not panic mid-batch
+    let x = layout.anchor_x.saturating_sub(resized_poster.width()) as i64;
+    image::imageops::overlay(&mut base, &resized_poster, x, layout.anchor_y);
 
     base
 }
 
-fn generate_painting(template: &DynamicImage, poster: &DynamicImage) -> DynamicImage {
+fn generate_painting(
+    template: &DynamicImage,
+    poster: &DynamicImage,
+    layout: PaintingLayout,
+) -> DynamicImage {
     let mut base = template.clone();
 
     // Resize painting image
-    let resized_painting = poster.resize_to_fill(243, 324, FilterType::Lanczos3);
+    let resized_painting =
+        poster.resize_to_fill(layout.fill_width, layout.fill_height, RESIZE_FILTER);
 
     // Overlay painting onto base
-    image::imageops::overlay(&mut base, &resized_painting, 264, 19);
+    image::imageops::overlay(
+        &mut base,
+        &resized_painting,
+        layout.overlay_x,
+        layout.overlay_y,
+    );
 
     base
 }